@@ -1,23 +1,106 @@
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 use mime_guess::from_path;
 use std::env;
 use std::error::Error;
-use std::fs;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
 use tiny_http::{Header, Request, Response, Server, StatusCode};
 
+/// Chunk size used when streaming file bodies, so serving large files
+/// doesn't require buffering them fully in memory.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// All responses are served through this boxed `Read` so that file streams
+/// and small in-memory (error/redirect/listing) bodies share one type.
+type BoxedResponse = Response<Box<dyn Read + Send>>;
+
+fn boxed_response(
+    status: u16,
+    reader: Box<dyn Read + Send>,
+    data_length: Option<u64>,
+) -> BoxedResponse {
+    Response::new(
+        StatusCode(status),
+        Vec::new(),
+        reader,
+        data_length.map(|len| len as usize),
+        None,
+    )
+}
+
+fn empty_response(status: u16) -> BoxedResponse {
+    boxed_response(status, Box::new(io::empty()), Some(0))
+}
+
+fn data_response(status: u16, data: Vec<u8>) -> BoxedResponse {
+    let len = data.len() as u64;
+    boxed_response(status, Box::new(io::Cursor::new(data)), Some(len))
+}
+
+const USAGE: &str =
+    "Usage: srvplz [--no-index] [--compress] [--strip-prefix <N> | --mount <prefix>] [directory]";
+
 fn main() {
+    let mut base_dir_arg = None;
+    let mut autoindex = true;
+    let mut compress = false;
+    let mut mount_prefix = None;
+
     let mut args = env::args_os().skip(1);
-    let base_dir = match args.next() {
+    while let Some(arg) = args.next() {
+        match arg.to_str() {
+            Some("--no-index") | Some("-D") => autoindex = false,
+            Some("--compress") => compress = true,
+            Some("--strip-prefix") => {
+                if mount_prefix.is_some() {
+                    eprintln!("{USAGE}");
+                    std::process::exit(2);
+                }
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("{USAGE}");
+                    std::process::exit(2);
+                });
+                let count: usize = value
+                    .to_str()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_else(|| {
+                        eprintln!("--strip-prefix requires a non-negative integer");
+                        std::process::exit(2);
+                    });
+                mount_prefix = Some(PathMount::StripCount(count));
+            }
+            Some("--mount") => {
+                if mount_prefix.is_some() {
+                    eprintln!("{USAGE}");
+                    std::process::exit(2);
+                }
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("{USAGE}");
+                    std::process::exit(2);
+                });
+                let segments = value
+                    .to_string_lossy()
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                mount_prefix = Some(PathMount::Literal(segments));
+            }
+            _ if base_dir_arg.is_none() => base_dir_arg = Some(arg),
+            _ => {
+                eprintln!("{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let base_dir = match base_dir_arg {
         Some(arg) => PathBuf::from(arg),
         None => env::current_dir().expect("failed to get current dir"),
     };
 
-    if args.next().is_some() {
-        eprintln!("Usage: srvplz [directory]");
-        std::process::exit(2);
-    }
-
     let base_dir = match fs::canonicalize(&base_dir) {
         Ok(dir) => dir,
         Err(err) => {
@@ -38,7 +121,41 @@ fn main() {
     println!("Serving HTTP on :: port {port} (http://[::]:{port}/) ...");
 
     for request in server.incoming_requests() {
-        handle_request(request, &base_dir);
+        handle_request(request, &base_dir, autoindex, compress, &mount_prefix);
+    }
+}
+
+/// Which leading URL path segments to skip before resolving a request
+/// against the served directory, configured via `--strip-prefix <N>` (skip
+/// a fixed number of segments) or `--mount <prefix>` (require and strip a
+/// literal prefix, 404ing requests that don't start with it).
+enum PathMount {
+    StripCount(usize),
+    Literal(Vec<String>),
+}
+
+impl PathMount {
+    /// Strips the configured prefix from `segments`, returning the
+    /// remaining segments, or `None` if there are fewer segments than
+    /// `--strip-prefix` skips or a `--mount` prefix doesn't match.
+    fn strip<'a>(&self, segments: &[&'a str]) -> Option<Vec<&'a str>> {
+        match self {
+            PathMount::StripCount(count) => segments.get(*count..).map(<[&str]>::to_vec),
+            PathMount::Literal(expected) => {
+                if segments.len() < expected.len() {
+                    return None;
+                }
+                let matches = segments
+                    .iter()
+                    .zip(expected)
+                    .all(|(segment, exp)| segment == exp);
+                if matches {
+                    Some(segments[expected.len()..].to_vec())
+                } else {
+                    None
+                }
+            }
+        }
     }
 }
 
@@ -88,7 +205,13 @@ fn is_addr_in_use(err: &(dyn Error + 'static)) -> bool {
     false
 }
 
-fn handle_request(request: Request, base_dir: &Path) {
+fn handle_request(
+    request: Request,
+    base_dir: &Path,
+    autoindex: bool,
+    compress: bool,
+    mount_prefix: &Option<PathMount>,
+) {
     let method = request.method().as_str().to_string();
     let url = request.url().to_string();
     let (path, _query) = url.split_once('?').unwrap_or((url.as_str(), ""));
@@ -98,8 +221,18 @@ fn handle_request(request: Request, base_dir: &Path) {
         .map(|addr| addr.ip().to_string())
         .unwrap_or_else(|| "-".to_string());
 
+    let headers = RequestHeaders::from_request(&request);
+
     let (status, response) = match method.as_str() {
-        "GET" | "HEAD" => route_request(path, &method, base_dir),
+        "GET" | "HEAD" => route_request(
+            path,
+            &method,
+            base_dir,
+            &headers,
+            autoindex,
+            compress,
+            mount_prefix,
+        ),
         _ => {
             let mut response = response_with_status(405, &method, "Method Not Allowed");
             response.add_header(header("Allow", "GET, HEAD"));
@@ -111,14 +244,47 @@ fn handle_request(request: Request, base_dir: &Path) {
     log_request(&remote_addr, &method, path, version, status);
 }
 
+/// The subset of request headers that routing and conditional-request
+/// handling care about, extracted up front since `Request` is consumed by
+/// `respond` once a response has been built.
+struct RequestHeaders {
+    range: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<String>,
+    accept_encoding: Option<String>,
+}
+
+impl RequestHeaders {
+    fn from_request(request: &Request) -> Self {
+        let find = |name: &str| -> Option<String> {
+            request
+                .headers()
+                .iter()
+                .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+                .map(|h| h.value.as_str().to_string())
+        };
+
+        RequestHeaders {
+            range: find("Range"),
+            if_none_match: find("If-None-Match"),
+            if_modified_since: find("If-Modified-Since"),
+            accept_encoding: find("Accept-Encoding"),
+        }
+    }
+}
+
 fn route_request(
     path: &str,
     method: &str,
     base_dir: &Path,
-) -> (u16, Response<std::io::Cursor<Vec<u8>>>) {
+    headers: &RequestHeaders,
+    autoindex: bool,
+    compress: bool,
+    mount_prefix: &Option<PathMount>,
+) -> (u16, BoxedResponse) {
     if path.len() > 1 && path.ends_with('/') {
         let location = path.trim_end_matches('/');
-        let mut response = Response::from_data(Vec::new()).with_status_code(StatusCode(301));
+        let mut response = empty_response(301);
         response.add_header(header("Location", location));
         return (301, response);
     }
@@ -128,31 +294,79 @@ fn route_request(
         Err(_) => return (400, response_with_status(400, method, "Bad Request")),
     };
 
-    let target = if decoded == "/" {
-        base_dir.join("index.html")
+    let segments: Vec<&str> = if decoded == "/" {
+        Vec::new()
     } else {
-        let relative = &decoded[1..];
-        match sanitized_relative_path(relative) {
-            Some(rel) => base_dir.join(rel),
+        decoded[1..].split('/').collect()
+    };
+
+    let segments = match mount_prefix {
+        Some(mount) => match mount.strip(&segments) {
+            Some(segments) => segments,
             None => return (404, response_with_status(404, method, "Not Found")),
-        }
+        },
+        None => segments,
     };
 
-    let target = match fs::metadata(&target) {
+    let relative = segments.join("/");
+    let requested_dir = match sanitized_relative_path(&relative) {
+        Some(rel) => base_dir.join(rel),
+        None => return (404, response_with_status(404, method, "Not Found")),
+    };
+
+    let target = match fs::metadata(&requested_dir) {
         Ok(meta) if meta.is_dir() => {
-            let index = target.join("index.html");
+            let index = requested_dir.join("index.html");
             if index.is_file() {
                 index
+            } else if autoindex {
+                return match directory_listing_response(&requested_dir, &decoded, method) {
+                    Ok(response) => (200, response),
+                    Err(_) => (
+                        500,
+                        response_with_status(500, method, "Internal Server Error"),
+                    ),
+                };
             } else {
                 return (404, response_with_status(404, method, "Not Found"));
             }
         }
-        Ok(_) => target,
+        Ok(_) => requested_dir,
         Err(_) => return (404, response_with_status(404, method, "Not Found")),
     };
 
-    match build_file_response(&target, method) {
-        Ok(response) => (200, response),
+    let meta = match fs::metadata(&target) {
+        Ok(meta) => meta,
+        Err(_) => return (404, response_with_status(404, method, "Not Found")),
+    };
+
+    let cache_info = match file_cache_info(&meta) {
+        Ok(cache_info) => cache_info,
+        Err(_) => {
+            return (
+                500,
+                response_with_status(500, method, "Internal Server Error"),
+            )
+        }
+    };
+
+    if is_not_modified(&cache_info, headers) {
+        let mut response = empty_response(304);
+        response.add_header(header("ETag", &cache_info.etag));
+        response.add_header(header("Last-Modified", &cache_info.last_modified));
+        return (304, response);
+    }
+
+    match build_file_response(
+        &target,
+        &meta,
+        &cache_info,
+        method,
+        headers.range.as_deref(),
+        compress,
+        headers.accept_encoding.as_deref(),
+    ) {
+        Ok(result) => result,
         Err(_) => (
             500,
             response_with_status(500, method, "Internal Server Error"),
@@ -160,6 +374,221 @@ fn route_request(
     }
 }
 
+/// Weak validators for a file, used for `ETag`/`Last-Modified` caching headers.
+struct CacheInfo {
+    etag: String,
+    last_modified: String,
+    modified: SystemTime,
+}
+
+/// Derives a weak `ETag` (`W/"<len>-<mtime_secs>"`) and an HTTP-date
+/// `Last-Modified` value from a file's metadata.
+fn file_cache_info(meta: &fs::Metadata) -> std::io::Result<CacheInfo> {
+    let modified = meta.modified()?;
+    let mtime_secs = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    Ok(CacheInfo {
+        etag: format!("W/\"{}-{}\"", meta.len(), mtime_secs),
+        last_modified: format_http_date(modified),
+        modified,
+    })
+}
+
+fn format_http_date(time: SystemTime) -> String {
+    let datetime: DateTime<Utc> = time.into();
+    datetime.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Parses an HTTP-date (the IMF-fixdate form we emit, which `chrono`
+/// accepts via its RFC 2822 parser) into a `SystemTime`.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc).into())
+}
+
+/// Applies the conditional-request precedence rule: `If-None-Match` is
+/// checked first and, when present, `If-Modified-Since` is ignored.
+fn is_not_modified(cache_info: &CacheInfo, headers: &RequestHeaders) -> bool {
+    if let Some(if_none_match) = &headers.if_none_match {
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate == cache_info.etag);
+    }
+
+    if let Some(if_modified_since) = &headers.if_modified_since {
+        if let Some(since) = parse_http_date(if_modified_since) {
+            let secs_since_epoch = |time: SystemTime| {
+                time.duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            };
+            // HTTP-dates only carry whole-second precision, so compare
+            // at second resolution rather than the file's exact mtime.
+            return secs_since_epoch(cache_info.modified) <= secs_since_epoch(since);
+        }
+    }
+
+    false
+}
+
+/// One entry in a rendered directory listing.
+struct DirEntry {
+    name: String,
+    is_dir: bool,
+}
+
+/// Renders a minimal HTML directory listing for `dir`, used as the
+/// fallback when a directory has no `index.html`. `url_path` is the
+/// decoded, non-trailing-slash request path that resolved to `dir` (e.g.
+/// `/` or `/assets`), used to build entry links and the page title.
+fn directory_listing_response(
+    dir: &Path,
+    url_path: &str,
+    method: &str,
+) -> io::Result<BoxedResponse> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        entries.push(DirEntry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            is_dir: entry.file_type()?.is_dir(),
+        });
+    }
+    entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.cmp(&b.name),
+    });
+
+    let prefix = if url_path == "/" {
+        "/".to_string()
+    } else {
+        format!("{url_path}/")
+    };
+
+    let title = html_escape(url_path);
+    let mut body = String::new();
+    body.push_str("<!DOCTYPE html>\n<html>\n<head><title>Index of ");
+    body.push_str(&title);
+    body.push_str("</title></head>\n<body>\n<h1>Index of ");
+    body.push_str(&title);
+    body.push_str("</h1>\n<ul>\n");
+
+    if url_path != "/" {
+        let parent = url_path.rsplit_once('/').map_or("/", |(head, _)| {
+            if head.is_empty() {
+                "/"
+            } else {
+                head
+            }
+        });
+        body.push_str(&format!(
+            "<li><a href=\"{}\">../</a></li>\n",
+            percent_encode_path(parent)
+        ));
+    }
+
+    for entry in &entries {
+        let href = percent_encode_path(&format!("{prefix}{}", entry.name));
+        let name = html_escape(&entry.name);
+        if entry.is_dir {
+            body.push_str(&format!("<li><a href=\"{href}/\">{name}/</a></li>\n"));
+        } else {
+            body.push_str(&format!("<li><a href=\"{href}\">{name}</a></li>\n"));
+        }
+    }
+
+    body.push_str("</ul>\n</body>\n</html>\n");
+
+    let body_len = body.len();
+    let mut response = if method == "HEAD" {
+        empty_response(200)
+    } else {
+        data_response(200, body.into_bytes())
+    };
+
+    response.add_header(header("Content-Type", "text/html; charset=utf-8"));
+    if method == "HEAD" {
+        response.add_header(header("Content-Length", body_len.to_string()));
+    }
+    Ok(response)
+}
+
+/// Percent-encodes each `/`-separated segment of an already-decoded path.
+fn percent_encode_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+/// A single byte range resolved against the file's length, end-inclusive.
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+/// Parses a `Range` header value, supporting the single-range forms
+/// `bytes=a-b`, `bytes=a-`, and the suffix form `bytes=-n`. Returns `None`
+/// when the header is absent, malformed, or requests multiple ranges, in
+/// which case callers should fall back to a normal `200` response.
+fn parse_range(range_header: Option<&str>, len: u64) -> Option<Result<ByteRange, ()>> {
+    let value = range_header?.strip_prefix("bytes=")?;
+    if value.contains(',') {
+        return None;
+    }
+
+    let (start, end) = value.split_once('-')?;
+
+    if start.is_empty() {
+        let suffix: u64 = end.parse().ok()?;
+        if suffix == 0 || len == 0 {
+            return Some(Err(()));
+        }
+        let start = len.saturating_sub(suffix);
+        return Some(Ok(ByteRange {
+            start,
+            end: len - 1,
+        }));
+    }
+
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() {
+        len.saturating_sub(1)
+    } else {
+        end.parse().ok()?
+    };
+
+    if start > end || start >= len {
+        return Some(Err(()));
+    }
+
+    Some(Ok(ByteRange {
+        start,
+        end: end.min(len.saturating_sub(1)),
+    }))
+}
+
 fn sanitized_relative_path(path: &str) -> Option<PathBuf> {
     let rel = Path::new(path);
     for component in rel.components() {
@@ -171,37 +600,166 @@ fn sanitized_relative_path(path: &str) -> Option<PathBuf> {
     Some(rel.to_path_buf())
 }
 
+/// Minimum file size before `--compress` bothers gzip/deflate-encoding a
+/// response; small files aren't worth the CPU cost.
+const COMPRESSION_THRESHOLD: u64 = 1024;
+
 fn build_file_response(
     path: &Path,
+    meta: &fs::Metadata,
+    cache_info: &CacheInfo,
     method: &str,
-) -> std::io::Result<Response<std::io::Cursor<Vec<u8>>>> {
+    range_header: Option<&str>,
+    compress: bool,
+    accept_encoding: Option<&str>,
+) -> io::Result<(u16, BoxedResponse)> {
     let mime = from_path(path).first_or_octet_stream();
-    let content_type = header("Content-Type", mime.essence_str());
-    let len = fs::metadata(path)?.len();
+    let mime_str = mime.essence_str().to_string();
+    let content_type = header("Content-Type", &mime_str);
+    let len = meta.len();
 
-    let mut response = if method == "HEAD" {
-        Response::from_data(Vec::new()).with_status_code(StatusCode(200))
+    let range = match parse_range(range_header, len) {
+        Some(Ok(range)) => Some(range),
+        Some(Err(())) => {
+            let mut response = empty_response(416);
+            response.add_header(content_type);
+            response.add_header(header("Accept-Ranges", "bytes"));
+            response.add_header(header("Content-Range", format!("bytes */{len}")));
+            response.add_header(header("ETag", &cache_info.etag));
+            response.add_header(header("Last-Modified", &cache_info.last_modified));
+            return Ok((416, response));
+        }
+        None => None,
+    };
+
+    let compressible = compress && is_compressible(&mime_str);
+    let worth_compressing =
+        compressible && range.is_none() && method != "HEAD" && len > COMPRESSION_THRESHOLD;
+    let encoding = if worth_compressing {
+        negotiate_encoding(accept_encoding)
     } else {
-        let body = fs::read(path)?;
-        Response::from_data(body).with_status_code(StatusCode(200))
+        None
     };
 
+    let status = if range.is_some() { 206 } else { 200 };
+    let content_length = match &range {
+        Some(range) => range.end - range.start + 1,
+        None => len,
+    };
+
+    let (body, body_length): (Box<dyn Read + Send>, u64) = if method == "HEAD" {
+        (Box::new(io::empty()), 0)
+    } else if let Some(encoding) = encoding {
+        let compressed = compress_bytes(&fs::read(path)?, encoding)?;
+        let compressed_len = compressed.len() as u64;
+        (Box::new(io::Cursor::new(compressed)), compressed_len)
+    } else {
+        let mut file = File::open(path)?;
+        if let Some(range) = &range {
+            file.seek(SeekFrom::Start(range.start))?;
+        }
+        let reader = BufReader::with_capacity(STREAM_CHUNK_SIZE, file);
+        if range.is_some() {
+            (Box::new(reader.take(content_length)), content_length)
+        } else {
+            (Box::new(reader), len)
+        }
+    };
+
+    let mut response = boxed_response(status, body, Some(body_length));
     response.add_header(content_type);
-    if method == "HEAD" {
-        response.add_header(header("Content-Length", len.to_string()));
+    response.add_header(header("Accept-Ranges", "bytes"));
+    response.add_header(header("ETag", &cache_info.etag));
+    response.add_header(header("Last-Modified", &cache_info.last_modified));
+    if compressible {
+        response.add_header(header("Vary", "Accept-Encoding"));
     }
-    Ok(response)
+    if let Some(encoding) = encoding {
+        response.add_header(header("Content-Encoding", encoding));
+        response.add_header(header("Content-Length", body_length.to_string()));
+    }
+    if let Some(range) = &range {
+        response.add_header(header(
+            "Content-Range",
+            format!("bytes {}-{}/{}", range.start, range.end, len),
+        ));
+    }
+    if method == "HEAD" || (range.is_some() && encoding.is_none()) {
+        response.add_header(header("Content-Length", content_length.to_string()));
+    }
+    Ok((status, response))
 }
 
-fn response_with_status(
-    status: u16,
-    method: &str,
-    body: &str,
-) -> Response<std::io::Cursor<Vec<u8>>> {
+/// Compresses `data` with the negotiated `encoding` (`"gzip"` or `"deflate"`).
+///
+/// Requires `flate2` as a dependency alongside `chrono`, `mime_guess`,
+/// `tiny_http`, and `urlencoding`; this tree has no tracked `Cargo.toml`,
+/// so that requirement isn't currently declared anywhere.
+fn compress_bytes(data: &[u8], encoding: &str) -> io::Result<Vec<u8>> {
+    use flate2::write::{DeflateEncoder, GzEncoder};
+    use flate2::Compression;
+
+    let mut output = Vec::new();
+    if encoding == "gzip" {
+        let mut encoder = GzEncoder::new(&mut output, Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()?;
+    } else {
+        let mut encoder = DeflateEncoder::new(&mut output, Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()?;
+    }
+    Ok(output)
+}
+
+/// Only text-like MIME types benefit from compression.
+fn is_compressible(mime_str: &str) -> bool {
+    mime_str.starts_with("text/")
+        || matches!(
+            mime_str,
+            "application/javascript" | "application/json" | "image/svg+xml"
+        )
+}
+
+/// Picks an encoding `srvplz` supports from a client's `Accept-Encoding`
+/// header: gzip if accepted, else deflate, whatever their order. Codings
+/// explicitly rejected with `q=0` are skipped; any other q-value is
+/// otherwise ignored, which is good enough for a local dev server.
+fn negotiate_encoding(accept_encoding: Option<&str>) -> Option<&'static str> {
+    let offered: Vec<(&str, f32)> = accept_encoding?
+        .split(',')
+        .map(|encoding| {
+            let mut parts = encoding.split(';');
+            let coding = parts.next().unwrap_or("").trim();
+            let q = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(1.0);
+            (coding, q)
+        })
+        .collect();
+    let accepts = |name: &str| {
+        offered
+            .iter()
+            .any(|(coding, q)| coding.eq_ignore_ascii_case(name) && *q != 0.0)
+    };
+
+    if accepts("gzip") {
+        Some("gzip")
+    } else if accepts("deflate") {
+        Some("deflate")
+    } else {
+        None
+    }
+}
+
+fn response_with_status(status: u16, method: &str, body: &str) -> BoxedResponse {
     if method == "HEAD" {
-        Response::from_data(Vec::new()).with_status_code(StatusCode(status))
+        empty_response(status)
     } else {
-        Response::from_string(body).with_status_code(StatusCode(status))
+        let mut response = data_response(status, body.as_bytes().to_vec());
+        response.add_header(header("Content-Type", "text/plain; charset=utf-8"));
+        response
     }
 }
 